@@ -4,7 +4,7 @@ use std::fs::OpenOptions;
 use std::io::{Read, Write as IoWrite};
 use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
 use base64::Engine;
 use chrono::{DateTime, Utc};
@@ -16,15 +16,22 @@ use uuid::Uuid;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct ServerProfile {
+    #[serde(default)]
     id: String,
     name: String,
     host: String,
     user: Option<String>,
     port: Option<u16>,
     password: Option<String>,
+    #[serde(default)]
     tags: Vec<String>,
+    #[serde(default)]
     favorite: bool,
     last_used_at: Option<DateTime<Utc>>,
+    /// Path to a Lua script whose `build_command` assembles this profile's
+    /// connection argv. Falls back to the built-in default when unset.
+    #[serde(default)]
+    command_script: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -32,6 +39,368 @@ struct ServerProfile {
 struct DirEntry {
     name: String,
     is_dir: bool,
+    size: Option<u64>,
+    modified: Option<DateTime<Utc>>,
+}
+
+/// Structured failure surfaced to the frontend by every filesystem command.
+///
+/// Each backend maps its native errors into these variants so the UI can react
+/// to the *kind* of failure (offer a retry on `PermissionDenied`, a refresh on
+/// `NotFound`, …) instead of string-matching an ad-hoc message.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+enum FsError {
+    NotFound,
+    NotADirectory,
+    IsDirectory,
+    PermissionDenied,
+    InvalidPath,
+    AlreadyExists,
+    /// The remote host could not be reached (TCP connect / handshake failed).
+    Unreachable,
+    /// The SSH session could not be authenticated (bad password / no agent key).
+    AuthFailed,
+    Backend(String),
+}
+
+impl std::fmt::Display for FsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FsError::NotFound => write!(f, "no such file or directory"),
+            FsError::NotADirectory => write!(f, "not a directory"),
+            FsError::IsDirectory => write!(f, "is a directory"),
+            FsError::PermissionDenied => write!(f, "permission denied"),
+            FsError::InvalidPath => write!(f, "invalid path"),
+            FsError::AlreadyExists => write!(f, "already exists"),
+            FsError::Unreachable => write!(f, "host unreachable"),
+            FsError::AuthFailed => write!(f, "authentication failed"),
+            FsError::Backend(detail) => write!(f, "{detail}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for FsError {
+    fn from(err: std::io::Error) -> Self {
+        use std::io::ErrorKind;
+        match err.kind() {
+            ErrorKind::NotFound => FsError::NotFound,
+            ErrorKind::PermissionDenied => FsError::PermissionDenied,
+            ErrorKind::AlreadyExists => FsError::AlreadyExists,
+            _ => FsError::Backend(err.to_string()),
+        }
+    }
+}
+
+/// Abstraction over a filesystem the file manager operates on, so the same
+/// commands drive both the machine running the app (`LocalFs`) and a remote
+/// host reached over SFTP (`SftpFs`).
+trait Vfs {
+    fn read_dir(&self, path: &str) -> Result<Vec<DirEntry>, FsError>;
+    fn rename(&self, from: &str, to: &str) -> Result<(), FsError>;
+    fn remove(&self, path: &str) -> Result<(), FsError>;
+    fn create_file(&self, path: &str) -> Result<(), FsError>;
+    fn create_dir(&self, path: &str) -> Result<(), FsError>;
+    fn stat(&self, path: &str) -> Result<DirEntry, FsError>;
+}
+
+/// Filesystem on the machine running the app, backed by `std::fs`.
+struct LocalFs;
+
+impl LocalFs {
+    fn entry_from_metadata(name: String, metadata: &fs::Metadata) -> DirEntry {
+        DirEntry {
+            name,
+            is_dir: metadata.is_dir(),
+            size: (!metadata.is_dir()).then(|| metadata.len()),
+            modified: metadata.modified().ok().map(DateTime::<Utc>::from),
+        }
+    }
+}
+
+impl Vfs for LocalFs {
+    fn read_dir(&self, path: &str) -> Result<Vec<DirEntry>, FsError> {
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(path)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            match entry.metadata() {
+                Ok(metadata) => entries.push(Self::entry_from_metadata(name, &metadata)),
+                Err(_) => entries.push(DirEntry {
+                    name,
+                    is_dir: false,
+                    size: None,
+                    modified: None,
+                }),
+            }
+        }
+        Ok(entries)
+    }
+
+    fn rename(&self, from: &str, to: &str) -> Result<(), FsError> {
+        Ok(fs::rename(from, to)?)
+    }
+
+    fn remove(&self, path: &str) -> Result<(), FsError> {
+        if fs::symlink_metadata(path)?.is_dir() {
+            fs::remove_dir_all(path)?;
+        } else {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    fn create_file(&self, path: &str) -> Result<(), FsError> {
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)?;
+        Ok(())
+    }
+
+    fn create_dir(&self, path: &str) -> Result<(), FsError> {
+        Ok(fs::create_dir(path)?)
+    }
+
+    fn stat(&self, path: &str) -> Result<DirEntry, FsError> {
+        let metadata = fs::metadata(path)?;
+        let name = Path::new(path)
+            .file_name()
+            .map(|value| value.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.to_owned());
+        Ok(Self::entry_from_metadata(name, &metadata))
+    }
+}
+
+/// Filesystem on a remote host, reached over an SSH/SFTP session opened from a
+/// [`ServerProfile`]'s stored host/user/port/password.
+struct SftpFs {
+    sftp: ssh2::Sftp,
+    // Held so the underlying SSH connection lives as long as the SFTP channel.
+    _session: ssh2::Session,
+}
+
+impl SftpFs {
+    fn connect(profile: &ServerProfile) -> Result<Self, FsError> {
+        let port = profile.port.unwrap_or(22);
+        let tcp = std::net::TcpStream::connect((profile.host.as_str(), port))
+            .map_err(|_| FsError::Unreachable)?;
+
+        let mut session = ssh2::Session::new().map_err(Self::map_ssh)?;
+        session.set_tcp_stream(tcp);
+        session.handshake().map_err(|_| FsError::Unreachable)?;
+
+        let user = profile.user.as_deref().unwrap_or("root");
+        match &profile.password {
+            Some(password) => session
+                .userauth_password(user, password)
+                .map_err(|_| FsError::AuthFailed)?,
+            None => session
+                .userauth_agent(user)
+                .map_err(|_| FsError::AuthFailed)?,
+        }
+
+        if !session.authenticated() {
+            return Err(FsError::AuthFailed);
+        }
+
+        let sftp = session.sftp().map_err(Self::map_ssh)?;
+        Ok(Self {
+            sftp,
+            _session: session,
+        })
+    }
+
+    fn map_ssh(err: ssh2::Error) -> FsError {
+        use ssh2::ErrorCode;
+        // libssh2 reports missing paths / permission problems through the SFTP
+        // status subcode; fall back to an opaque backend error otherwise.
+        match err.code() {
+            ErrorCode::SFTP(2) => FsError::NotFound,
+            ErrorCode::SFTP(3) => FsError::PermissionDenied,
+            ErrorCode::SFTP(11) => FsError::AlreadyExists,
+            _ => FsError::Backend(err.message().to_string()),
+        }
+    }
+
+    fn entry_from_stat(name: String, stat: &ssh2::FileStat) -> DirEntry {
+        let is_dir = stat.is_dir();
+        DirEntry {
+            name,
+            is_dir,
+            size: (!is_dir).then_some(()).and(stat.size),
+            modified: stat
+                .mtime
+                .and_then(|secs| DateTime::from_timestamp(secs as i64, 0)),
+        }
+    }
+}
+
+impl Vfs for SftpFs {
+    fn read_dir(&self, path: &str) -> Result<Vec<DirEntry>, FsError> {
+        let mut entries = Vec::new();
+        for (child, stat) in self.sftp.readdir(Path::new(path)).map_err(Self::map_ssh)? {
+            let name = child
+                .file_name()
+                .map(|value| value.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            entries.push(Self::entry_from_stat(name, &stat));
+        }
+        Ok(entries)
+    }
+
+    fn rename(&self, from: &str, to: &str) -> Result<(), FsError> {
+        self.sftp
+            .rename(Path::new(from), Path::new(to), None)
+            .map_err(Self::map_ssh)
+    }
+
+    fn remove(&self, path: &str) -> Result<(), FsError> {
+        let target = Path::new(path);
+        let stat = self.sftp.stat(target).map_err(Self::map_ssh)?;
+        if stat.is_dir() {
+            // SFTP has no recursive remove; clear the directory depth-first.
+            for (child, _) in self.sftp.readdir(target).map_err(Self::map_ssh)? {
+                self.remove(&child.to_string_lossy())?;
+            }
+            self.sftp.rmdir(target).map_err(Self::map_ssh)
+        } else {
+            self.sftp.unlink(target).map_err(Self::map_ssh)
+        }
+    }
+
+    fn create_file(&self, path: &str) -> Result<(), FsError> {
+        self.sftp.create(Path::new(path)).map(drop).map_err(Self::map_ssh)
+    }
+
+    fn create_dir(&self, path: &str) -> Result<(), FsError> {
+        self.sftp.mkdir(Path::new(path), 0o755).map_err(Self::map_ssh)
+    }
+
+    fn stat(&self, path: &str) -> Result<DirEntry, FsError> {
+        let stat = self.sftp.stat(Path::new(path)).map_err(Self::map_ssh)?;
+        let name = Path::new(path)
+            .file_name()
+            .map(|value| value.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.to_owned());
+        Ok(Self::entry_from_stat(name, &stat))
+    }
+}
+
+/// A cached SFTP connection shared between file commands. Delegates each
+/// operation to the underlying [`SftpFs`], and on a connection-level failure
+/// evicts itself from the cache so the next command reconnects cleanly.
+struct SharedSftp {
+    cache: SftpCache,
+    profile_id: String,
+    handle: Arc<Mutex<SftpFs>>,
+}
+
+impl SharedSftp {
+    fn run<T>(&self, op: impl FnOnce(&SftpFs) -> Result<T, FsError>) -> Result<T, FsError> {
+        let result = {
+            let session = self
+                .handle
+                .lock()
+                .map_err(|_| FsError::Backend("sftp session poisoned".into()))?;
+            op(&session)
+        };
+        // A `Backend` error may mean the channel itself broke (the other
+        // variants are per-operation outcomes the session survives); drop it so
+        // the next command opens a fresh one. `Backend` also carries SFTP
+        // statuses with no dedicated variant, so those reconnect as well.
+        if matches!(result, Err(FsError::Backend(_))) {
+            if let Ok(mut cache) = self.cache.lock() {
+                if cache
+                    .get(&self.profile_id)
+                    .is_some_and(|existing| Arc::ptr_eq(existing, &self.handle))
+                {
+                    cache.remove(&self.profile_id);
+                }
+            }
+        }
+        result
+    }
+}
+
+impl Vfs for SharedSftp {
+    fn read_dir(&self, path: &str) -> Result<Vec<DirEntry>, FsError> {
+        self.run(|fs| fs.read_dir(path))
+    }
+
+    fn rename(&self, from: &str, to: &str) -> Result<(), FsError> {
+        self.run(|fs| fs.rename(from, to))
+    }
+
+    fn remove(&self, path: &str) -> Result<(), FsError> {
+        self.run(|fs| fs.remove(path))
+    }
+
+    fn create_file(&self, path: &str) -> Result<(), FsError> {
+        self.run(|fs| fs.create_file(path))
+    }
+
+    fn create_dir(&self, path: &str) -> Result<(), FsError> {
+        self.run(|fs| fs.create_dir(path))
+    }
+
+    fn stat(&self, path: &str) -> Result<DirEntry, FsError> {
+        self.run(|fs| fs.stat(path))
+    }
+}
+
+/// Resolve the filesystem backend a command should operate on: the local disk
+/// when no profile is named, or that profile's (cached) SFTP session when it is.
+fn vfs_for(
+    state: &tauri::State<'_, AppState>,
+    profile_id: Option<String>,
+) -> Result<Box<dyn Vfs>, FsError> {
+    let Some(id) = profile_id else {
+        return Ok(Box::new(LocalFs));
+    };
+
+    // Fast path: reuse a live connection for this profile.
+    {
+        let cache = state
+            .sftp_sessions
+            .lock()
+            .map_err(|_| FsError::Backend("sftp cache poisoned".into()))?;
+        if let Some(existing) = cache.get(&id) {
+            return Ok(Box::new(SharedSftp {
+                cache: state.sftp_sessions.clone(),
+                profile_id: id,
+                handle: existing.clone(),
+            }));
+        }
+    }
+
+    // Slow path: clone the profile and release the lock before connecting — the
+    // handshake blocks for seconds (or a full TCP timeout on an unreachable
+    // host), and every other profile command shares this lock.
+    let profile = {
+        let profiles = state
+            .profiles
+            .lock()
+            .map_err(|_| FsError::Backend("profile state poisoned".into()))?;
+        profiles
+            .iter()
+            .find(|candidate| candidate.id == id)
+            .cloned()
+            .ok_or(FsError::NotFound)?
+    };
+
+    let handle = Arc::new(Mutex::new(SftpFs::connect(&profile)?));
+    state
+        .sftp_sessions
+        .lock()
+        .map_err(|_| FsError::Backend("sftp cache poisoned".into()))?
+        .insert(id.clone(), handle.clone());
+
+    Ok(Box::new(SharedSftp {
+        cache: state.sftp_sessions.clone(),
+        profile_id: id,
+        handle,
+    }))
 }
 
 struct PtySession {
@@ -39,10 +408,20 @@ struct PtySession {
     writer: Box<dyn IoWrite + Send>,
 }
 
+/// Shared handle to a cached SFTP connection, keyed by `profile_id`.
+type SftpCache = Arc<Mutex<HashMap<String, Arc<Mutex<SftpFs>>>>>;
+
 struct AppState {
     profiles: Mutex<Vec<ServerProfile>>,
     data_path: Mutex<Option<PathBuf>>,
     pty_sessions: Mutex<HashMap<String, PtySession>>,
+    /// Broadcast groups: a group id mapped to its member session ids. Input sent
+    /// with `write_group` is fanned out to every member at once.
+    session_groups: Mutex<HashMap<String, Vec<String>>>,
+    /// Live SFTP connections reused across file commands so each navigate/rename
+    /// doesn't pay a fresh TCP + handshake + auth. Entries are evicted when a
+    /// connection-level error shows the session is no longer usable.
+    sftp_sessions: SftpCache,
 }
 
 impl Default for AppState {
@@ -51,29 +430,168 @@ impl Default for AppState {
             profiles: Mutex::new(Vec::new()),
             data_path: Mutex::new(None),
             pty_sessions: Mutex::new(HashMap::new()),
+            session_groups: Mutex::new(HashMap::new()),
+            sftp_sessions: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
 
+/// Coarse classification of a failure so the frontend can react to the *kind*
+/// of problem — prompt for credentials on `AuthFailed`, suggest installing a
+/// tool on `MissingDependency`, offer a reconnect on `HostUnreachable` — rather
+/// than parsing a flattened message.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+enum ErrorKind {
+    AuthFailed,
+    HostUnreachable,
+    MissingDependency,
+    Permission,
+    NotFound,
+    Io,
+    Internal,
+}
+
+/// Error returned by every `#[tauri::command]`. `context` carries the
+/// `caused by:` frames accumulated as the error bubbled up, outermost last.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AppError {
+    kind: ErrorKind,
+    message: String,
+    context: Vec<String>,
+}
+
+impl AppError {
+    fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            context: Vec::new(),
+        }
+    }
+
+    fn internal(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Internal, message)
+    }
+
+    fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::NotFound, message)
+    }
+
+    /// A required external program (`sshpass`, `code`, `alacritty`, …) is not
+    /// installed or not on `PATH`.
+    fn missing_dependency(program: &str) -> Self {
+        Self::new(
+            ErrorKind::MissingDependency,
+            format!("required program '{program}' is not installed or not on PATH"),
+        )
+    }
+
+    /// Attach a `caused by:` frame describing what we were trying to do.
+    fn with_context(mut self, frame: impl Into<String>) -> Self {
+        self.context.push(frame.into());
+        self
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        AppError::internal(message)
+    }
+}
+
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        AppError::internal(message)
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(err: std::io::Error) -> Self {
+        use std::io::ErrorKind::*;
+        let kind = match err.kind() {
+            PermissionDenied => ErrorKind::Permission,
+            NotFound => ErrorKind::NotFound,
+            ConnectionRefused | ConnectionReset | ConnectionAborted | TimedOut
+            | NotConnected | AddrNotAvailable => ErrorKind::HostUnreachable,
+            _ => ErrorKind::Io,
+        };
+        AppError::new(kind, err.to_string())
+    }
+}
+
+impl From<FsError> for AppError {
+    fn from(err: FsError) -> Self {
+        let kind = match err {
+            FsError::NotFound => ErrorKind::NotFound,
+            FsError::PermissionDenied => ErrorKind::Permission,
+            FsError::Unreachable => ErrorKind::HostUnreachable,
+            FsError::AuthFailed => ErrorKind::AuthFailed,
+            _ => ErrorKind::Io,
+        };
+        AppError::new(kind, err.to_string())
+    }
+}
+
+/// Attach a `caused by:` frame to a fallible result, converting the underlying
+/// error into an [`AppError`] on the way — the Rust analogue of wrapping an
+/// error with "while doing X".
+trait ResultExt<T> {
+    fn context(self, frame: impl Into<String>) -> Result<T, AppError>;
+    fn with_context<F, S>(self, frame: F) -> Result<T, AppError>
+    where
+        F: FnOnce() -> S,
+        S: Into<String>;
+}
+
+impl<T, E: Into<AppError>> ResultExt<T> for Result<T, E> {
+    fn context(self, frame: impl Into<String>) -> Result<T, AppError> {
+        self.map_err(|err| err.into().with_context(frame))
+    }
+
+    fn with_context<F, S>(self, frame: F) -> Result<T, AppError>
+    where
+        F: FnOnce() -> S,
+        S: Into<String>,
+    {
+        self.map_err(|err| err.into().with_context(frame()))
+    }
+}
+
+/// Spawn `command`, reporting a missing executable as `MissingDependency`
+/// (`sshpass`/`code`/`alacritty` not installed) rather than a bare `NotFound`.
+fn spawn_command(command: &mut Command, program: &str) -> Result<std::process::Child, AppError> {
+    command.spawn().map_err(|err| match err.kind() {
+        std::io::ErrorKind::NotFound => AppError::missing_dependency(program),
+        _ => AppError::from(err),
+    })
+}
+
 #[tauri::command]
-fn list_profiles(state: tauri::State<'_, AppState>) -> Result<Vec<ServerProfile>, String> {
+fn list_profiles(state: tauri::State<'_, AppState>) -> Result<Vec<ServerProfile>, AppError> {
     let profiles = state.profiles.lock().map_err(|_| "profile state poisoned")?;
     Ok(profiles.clone())
 }
 
 #[tauri::command]
-fn upsert_profile(state: tauri::State<'_, AppState>, profile: ServerProfile) -> Result<(), String> {
+fn upsert_profile(state: tauri::State<'_, AppState>, profile: ServerProfile) -> Result<(), AppError> {
     let mut profiles = state.profiles.lock().map_err(|_| "profile state poisoned")?;
     if let Some(existing) = profiles.iter_mut().find(|candidate| candidate.id == profile.id) {
+        // Host/credentials may have changed; drop any cached SFTP session so the
+        // next file command reconnects with the new settings.
+        if let Ok(mut cache) = state.sftp_sessions.lock() {
+            cache.remove(&profile.id);
+        }
         *existing = profile;
     } else {
         profiles.push(profile);
     }
-    persist_profiles(&state, &profiles)
+    persist_profiles(&state, &profiles).context("while saving the profile store")
 }
 
 #[tauri::command]
-fn add_profile_from_csv(state: tauri::State<'_, AppState>, csv_line: String) -> Result<(), String> {
+fn add_profile_from_csv(state: tauri::State<'_, AppState>, csv_line: String) -> Result<(), AppError> {
     let fields = csv_line
         .split(',')
         .map(|segment| segment.trim().to_owned())
@@ -93,131 +611,470 @@ fn add_profile_from_csv(state: tauri::State<'_, AppState>, csv_line: String) ->
         tags: Vec::new(),
         favorite: false,
         last_used_at: None,
+        command_script: None,
     };
 
     let mut profiles = state.profiles.lock().map_err(|_| "profile state poisoned")?;
     profiles.insert(0, profile);
-    persist_profiles(&state, &profiles)
+    persist_profiles(&state, &profiles).context("while saving the profile store")
+}
+
+/// The on-disk shape of an exported profile set: a `[[server]]` table per
+/// profile, matching the config-file ergonomics (hand-editable, diffable) that
+/// tools like this are expected to offer.
+#[derive(Serialize, Deserialize)]
+struct ProfilesDocument {
+    #[serde(default, rename = "server")]
+    servers: Vec<ServerProfile>,
+}
+
+/// Identity under which an imported profile is considered the *same* host as an
+/// existing one — the port defaults to 22 so an omitted `port` still matches an
+/// explicit `:22`.
+fn profile_key(profile: &ServerProfile) -> (String, Option<String>, u16) {
+    (
+        profile.host.clone(),
+        profile.user.clone(),
+        profile.port.unwrap_or(22),
+    )
+}
+
+/// Fold `incoming` into `existing`, refreshing a profile in place when its
+/// (host, user, port) already exists rather than appending a duplicate. A
+/// matched profile keeps its stored `id` so tabs keyed on it survive a
+/// re-import; every other field, `favorite` included, is taken from the incoming
+/// profile. A fresh one is assigned a UUID if it arrived without one.
+fn merge_profiles(existing: &mut Vec<ServerProfile>, incoming: Vec<ServerProfile>) {
+    for mut profile in incoming {
+        if let Some(slot) = existing
+            .iter_mut()
+            .find(|candidate| profile_key(candidate) == profile_key(&profile))
+        {
+            profile.id = slot.id.clone();
+            *slot = profile;
+        } else {
+            if profile.id.is_empty() {
+                profile.id = Uuid::new_v4().to_string();
+            }
+            existing.push(profile);
+        }
+    }
 }
 
 #[tauri::command]
-fn connect_profile(state: tauri::State<'_, AppState>, id: String) -> Result<(), String> {
+fn export_profiles_toml(state: tauri::State<'_, AppState>) -> Result<String, AppError> {
+    let profiles = state.profiles.lock().map_err(|_| "profile state poisoned")?;
+    let document = ProfilesDocument {
+        servers: profiles.clone(),
+    };
+    toml::to_string_pretty(&document)
+        .map_err(|err| AppError::internal(format!("Failed to encode profiles: {err}")))
+        .context("while exporting the profile store")
+}
+
+#[tauri::command]
+fn import_profiles_toml(
+    state: tauri::State<'_, AppState>,
+    toml: String,
+) -> Result<usize, AppError> {
+    let document: ProfilesDocument = toml::from_str(&toml)
+        .map_err(|err| AppError::internal(format!("Failed to parse profiles TOML: {err}")))?;
+    let count = document.servers.len();
+
+    let mut profiles = state.profiles.lock().map_err(|_| "profile state poisoned")?;
+    merge_profiles(&mut profiles, document.servers);
+    persist_profiles(&state, &profiles).context("while saving the profile store")?;
+    Ok(count)
+}
+
+/// Parse an `ssh_config`(5) document into one profile per concrete `Host`
+/// stanza, named after the first alias on its `Host` line (further aliases on
+/// the same line are not turned into profiles). Wildcard patterns (`Host *.example.com`) describe defaults rather than
+/// a connectable host, so they are skipped. `ProxyJump`/`IdentityFile` have no
+/// dedicated profile field and are preserved as `key:value` tags.
+fn parse_ssh_config(content: &str) -> Vec<ServerProfile> {
+    let mut profiles = Vec::new();
+    let mut current: Option<ServerProfile> = None;
+    let mut skip = false;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let Some((keyword, value)) = line.split_once(|c: char| c == ' ' || c == '\t' || c == '=')
+        else {
+            continue;
+        };
+        let value = value.trim();
+
+        match keyword.trim().to_ascii_lowercase().as_str() {
+            "host" => {
+                if let Some(profile) = current.take() {
+                    if !skip {
+                        profiles.push(profile);
+                    }
+                }
+                // A `Host` line may list several patterns; treat the stanza as a
+                // glob default (and skip it) if any of them is a wildcard.
+                skip = value
+                    .split_whitespace()
+                    .any(|pattern| pattern.contains('*') || pattern.contains('?'));
+                let alias = value.split_whitespace().next().unwrap_or_default().to_string();
+                current = Some(ServerProfile {
+                    id: String::new(),
+                    name: alias.clone(),
+                    host: alias,
+                    user: None,
+                    port: None,
+                    password: None,
+                    tags: Vec::new(),
+                    favorite: false,
+                    last_used_at: None,
+                    command_script: None,
+                });
+            }
+            "hostname" => {
+                if let Some(profile) = current.as_mut() {
+                    profile.host = value.to_string();
+                }
+            }
+            "user" => {
+                if let Some(profile) = current.as_mut() {
+                    profile.user = Some(value.to_string());
+                }
+            }
+            "port" => {
+                if let Some(profile) = current.as_mut() {
+                    if let Ok(port) = value.parse() {
+                        profile.port = Some(port);
+                    }
+                }
+            }
+            "proxyjump" => {
+                if let Some(profile) = current.as_mut() {
+                    profile.tags.push(format!("proxyjump:{value}"));
+                }
+            }
+            "identityfile" => {
+                if let Some(profile) = current.as_mut() {
+                    profile.tags.push(format!("identityfile:{value}"));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(profile) = current.take() {
+        if !skip {
+            profiles.push(profile);
+        }
+    }
+
+    profiles
+}
+
+#[tauri::command]
+fn import_ssh_config(state: tauri::State<'_, AppState>) -> Result<usize, AppError> {
+    let home = std::env::var_os("HOME")
+        .ok_or_else(|| AppError::not_found("HOME is not set"))?;
+    let path = PathBuf::from(home).join(".ssh").join("config");
+
+    let content = fs::read_to_string(&path)
+        .map_err(AppError::from)
+        .with_context(|| format!("while reading {}", path.display()))?;
+    let incoming = parse_ssh_config(&content);
+    let count = incoming.len();
+
+    let mut profiles = state.profiles.lock().map_err(|_| "profile state poisoned")?;
+    merge_profiles(&mut profiles, incoming);
+    persist_profiles(&state, &profiles).context("while saving the profile store")?;
+    Ok(count)
+}
+
+/// A fully assembled connection command: the program to exec plus its argv and
+/// any environment overrides. Both the PTY path (`CommandBuilder`) and the
+/// detached-Alacritty path (`Command`) are built from one of these.
+struct ResolvedCommand {
+    program: String,
+    args: Vec<String>,
+    env: Vec<(String, String)>,
+}
+
+/// Default argv assembly — the hardcoded `sshpass`/`ssh` behaviour used when
+/// the Lua layer is compiled out.
+#[cfg(not(feature = "lua"))]
+fn default_connection_command(profile: &ServerProfile) -> ResolvedCommand {
+    let target = match &profile.user {
+        Some(user) if !user.is_empty() => format!("{user}@{}", profile.host),
+        _ => profile.host.clone(),
+    };
+    let port = profile.port.unwrap_or(22).to_string();
+
+    let mut command = match &profile.password {
+        Some(password) if !password.is_empty() => ResolvedCommand {
+            program: "sshpass".to_string(),
+            args: vec!["-p".to_string(), password.clone(), "ssh".to_string()],
+            env: Vec::new(),
+        },
+        _ => ResolvedCommand {
+            program: "ssh".to_string(),
+            args: Vec::new(),
+            env: Vec::new(),
+        },
+    };
+    command.args.extend([
+        "-o".to_string(),
+        "StrictHostKeyChecking=no".to_string(),
+        "-p".to_string(),
+        port,
+        target,
+    ]);
+    command
+}
+
+/// The script shipped by default; reproduces [`default_connection_command`] so
+/// a Lua-enabled build with no custom script behaves exactly like the native
+/// one. Users override it per profile via `command_script`.
+#[cfg(feature = "lua")]
+const DEFAULT_COMMAND_SCRIPT: &str = r#"
+function build_command(profile, cmd)
+  local port = profile.port or 22
+  local target = profile.host
+  if profile.user ~= nil and profile.user ~= "" then
+    target = profile.user .. "@" .. profile.host
+  end
+  if profile.password ~= nil and profile.password ~= "" then
+    cmd:program("sshpass")
+    cmd:arg("-p")
+    cmd:arg(profile.password)
+    cmd:arg("ssh")
+  else
+    cmd:program("ssh")
+  end
+  cmd:arg("-o")
+  cmd:arg("StrictHostKeyChecking=no")
+  cmd:arg("-p")
+  cmd:arg(tostring(port))
+  cmd:arg(target)
+end
+"#;
+
+/// Embedded Lua layer: runs a user `build_command(profile, cmd)` script and
+/// collects the program/argv/env it pushes onto the `cmd` builder.
+#[cfg(feature = "lua")]
+mod lua_command {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use mlua::{Lua, UserData, UserDataMethods};
+
+    use super::{ResolvedCommand, ServerProfile};
+
+    #[derive(Default)]
+    struct Collected {
+        program: Option<String>,
+        args: Vec<String>,
+        env: Vec<(String, String)>,
+    }
+
+    /// Builder handed to the script, mirroring the `vm:arg(...)` pattern.
+    struct CommandHandle(Rc<RefCell<Collected>>);
+
+    impl UserData for CommandHandle {
+        fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+            methods.add_method("program", |_, this, program: String| {
+                this.0.borrow_mut().program = Some(program);
+                Ok(())
+            });
+            methods.add_method("arg", |_, this, arg: String| {
+                this.0.borrow_mut().args.push(arg);
+                Ok(())
+            });
+            methods.add_method("env", |_, this, (key, value): (String, String)| {
+                this.0.borrow_mut().env.push((key, value));
+                Ok(())
+            });
+        }
+    }
+
+    pub(super) fn build(script: &str, profile: &ServerProfile) -> Result<ResolvedCommand, String> {
+        let lua = Lua::new();
+
+        let table = lua.create_table().map_err(|err| err.to_string())?;
+        table.set("host", profile.host.clone()).map_err(|err| err.to_string())?;
+        table.set("user", profile.user.clone()).map_err(|err| err.to_string())?;
+        table.set("port", profile.port).map_err(|err| err.to_string())?;
+        table.set("password", profile.password.clone()).map_err(|err| err.to_string())?;
+        table.set("tags", profile.tags.clone()).map_err(|err| err.to_string())?;
+
+        lua.load(script)
+            .exec()
+            .map_err(|err| format!("command script failed to load: {err}"))?;
+
+        let build_command: mlua::Function = lua
+            .globals()
+            .get("build_command")
+            .map_err(|_| "command script does not define build_command".to_string())?;
+
+        let collected = Rc::new(RefCell::new(Collected::default()));
+        build_command
+            .call::<()>((table, CommandHandle(collected.clone())))
+            .map_err(|err| format!("build_command raised an error: {err}"))?;
+
+        let collected = Rc::try_unwrap(collected)
+            .map(RefCell::into_inner)
+            .unwrap_or_default();
+        let program = collected
+            .program
+            .ok_or_else(|| "build_command did not set a program".to_string())?;
+        Ok(ResolvedCommand {
+            program,
+            args: collected.args,
+            env: collected.env,
+        })
+    }
+}
+
+/// Resolve the argv for connecting to `profile`. With the `lua` feature enabled
+/// this runs the profile's `command_script`, or `DEFAULT_COMMAND_SCRIPT` when
+/// none is configured; without it, the native default.
+fn resolve_connection_command(profile: &ServerProfile) -> Result<ResolvedCommand, String> {
+    #[cfg(feature = "lua")]
+    {
+        let script = match &profile.command_script {
+            Some(path) => fs::read_to_string(path)
+                .map_err(|err| format!("Failed to read command script {path}: {err}"))?,
+            None => DEFAULT_COMMAND_SCRIPT.to_string(),
+        };
+        lua_command::build(&script, profile)
+    }
+    #[cfg(not(feature = "lua"))]
+    {
+        Ok(default_connection_command(profile))
+    }
+}
+
+#[tauri::command]
+fn connect_profile(state: tauri::State<'_, AppState>, id: String) -> Result<(), AppError> {
     let mut profiles = state.profiles.lock().map_err(|_| "profile state poisoned")?;
     let profile = profiles
         .iter_mut()
         .find(|candidate| candidate.id == id)
-        .ok_or_else(|| "Profile not found".to_string())?;
+        .ok_or_else(|| AppError::not_found("Profile not found"))?;
 
     profile.last_used_at = Some(Utc::now());
+    let host = profile.host.clone();
 
-    let target = match &profile.user {
-        Some(user) => format!("{user}@{}", profile.host),
-        None => profile.host.clone(),
-    };
+    let resolved = resolve_connection_command(profile)
+        .with_context(|| format!("while connecting to {host}"))?;
 
     let mut command = Command::new("alacritty");
     command.arg("-e");
-
-    if let Some(password) = &profile.password {
-        command.arg("sh").arg("-lc").arg(format!(
-            "sshpass -p '{}' ssh -p {} {}",
-            escape_shell(password),
-            profile.port.unwrap_or(22),
-            target
-        ));
-    } else {
-        command.arg("ssh");
-        command.arg("-p");
-        command.arg(profile.port.unwrap_or(22).to_string());
-        command.arg(target);
+    command.arg(&resolved.program);
+    for arg in &resolved.args {
+        command.arg(arg);
+    }
+    for (key, value) in &resolved.env {
+        command.env(key, value);
     }
 
-    command.spawn().map_err(|err| format!("Failed to launch connection: {err}"))?;
-    persist_profiles(&state, &profiles)
+    spawn_command(&mut command, "alacritty")
+        .with_context(|| format!("while connecting to {host}"))?;
+    persist_profiles(&state, &profiles).context("while saving the profile store")
 }
 
 #[tauri::command]
-fn open_local_terminal() -> Result<(), String> {
-    Command::new("alacritty")
-        .spawn()
-        .map_err(|err| format!("Failed to launch local terminal: {err}"))?;
+fn open_local_terminal() -> Result<(), AppError> {
+    spawn_command(&mut Command::new("alacritty"), "alacritty")?;
     Ok(())
 }
 
 #[tauri::command]
-fn open_vscode(state: tauri::State<'_, AppState>, profile_id: Option<String>) -> Result<(), String> {
+fn open_vscode(state: tauri::State<'_, AppState>, profile_id: Option<String>) -> Result<(), AppError> {
     if let Some(pid) = profile_id {
         let profiles = state.profiles.lock().map_err(|_| "profile state poisoned")?;
         let profile = profiles
             .iter()
             .find(|p| p.id == pid)
-            .ok_or_else(|| "Profile not found".to_string())?;
+            .ok_or_else(|| AppError::not_found("Profile not found"))?;
 
         let target = match &profile.user {
             Some(user) => format!("{user}@{}", profile.host),
             None => profile.host.clone(),
         };
 
-        Command::new("code")
+        let mut command = Command::new("code");
+        command
             .arg("--remote")
             .arg(format!("ssh-remote+{}", target))
-            .arg("/")
-            .spawn()
-            .map_err(|err| format!("Failed to launch VS Code: {err}"))?;
+            .arg("/");
+        spawn_command(&mut command, "code")
+            .with_context(|| format!("while opening VS Code on {}", profile.host))?;
     } else {
-        Command::new("code")
-            .spawn()
-            .map_err(|err| format!("Failed to launch VS Code: {err}"))?;
+        spawn_command(&mut Command::new("code"), "code")?;
     }
     Ok(())
 }
 
+/// Why a PTY session ended, so the UI can offer a reconnect prompt for a
+/// dropped connection but stay quiet for an intentional logout.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+enum PtyExitReason {
+    /// Child closed the PTY and exited cleanly — an intentional `exit`/logout.
+    Eof,
+    /// Reading from the PTY failed before EOF — the connection dropped.
+    ReadError,
+    /// Child exited with a nonzero status — the session failed — or its exit
+    /// status could not be collected.
+    ChildExit,
+}
+
+/// Payload of the `pty-exit-<sid>` event.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PtyExit {
+    reason: PtyExitReason,
+    /// Child exit code when known (nonzero exits), otherwise `None`.
+    code: Option<u32>,
+    /// Human-readable detail for a read error, or for a failure to wait on the
+    /// child.
+    message: Option<String>,
+}
+
 #[tauri::command]
 fn spawn_pty(
     app: tauri::AppHandle,
     state: tauri::State<'_, AppState>,
     session_id: String,
     profile_id: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     // Build the command to run in the PTY
     let mut cmd = if let Some(ref pid) = profile_id {
         let mut profiles = state.profiles.lock().map_err(|_| "profile state poisoned")?;
         let profile = profiles
             .iter_mut()
             .find(|p| p.id == *pid)
-            .ok_or_else(|| "Profile not found".to_string())?;
+            .ok_or_else(|| AppError::not_found("Profile not found"))?;
 
         profile.last_used_at = Some(Utc::now());
+        let host = profile.host.clone();
 
-        let target = match &profile.user {
-            Some(user) => format!("{user}@{}", profile.host),
-            None => profile.host.clone(),
-        };
-        let port_str = profile.port.unwrap_or(22).to_string();
-
-        let cmd = if let Some(password) = &profile.password {
-            let mut c = CommandBuilder::new("sshpass");
-            c.arg("-p");
-            c.arg(password.as_str());
-            c.arg("ssh");
-            c.arg("-o");
-            c.arg("StrictHostKeyChecking=no");
-            c.arg("-p");
-            c.arg(&port_str);
-            c.arg(&target);
-            c
-        } else {
-            let mut c = CommandBuilder::new("ssh");
-            c.arg("-o");
-            c.arg("StrictHostKeyChecking=no");
-            c.arg("-p");
-            c.arg(&port_str);
-            c.arg(&target);
-            c
-        };
+        let resolved = resolve_connection_command(profile)
+            .with_context(|| format!("while connecting to {host}"))?;
+
+        let mut c = CommandBuilder::new(&resolved.program);
+        for arg in &resolved.args {
+            c.arg(arg);
+        }
+        for (key, value) in &resolved.env {
+            c.env(key, value);
+        }
 
         let _ = persist_profiles(&state, &profiles);
-        cmd
+        c
     } else {
         CommandBuilder::new_default_prog()
     };
@@ -237,12 +1094,12 @@ fn spawn_pty(
 
     let pair = pty_system
         .openpty(size)
-        .map_err(|e| format!("Failed to open PTY: {e}"))?;
+        .map_err(|e| AppError::internal(format!("Failed to open PTY: {e}")))?;
 
-    let _child = pair
+    let mut child = pair
         .slave
         .spawn_command(cmd)
-        .map_err(|e| format!("Failed to spawn command: {e}"))?;
+        .map_err(|e| AppError::internal(format!("Failed to spawn command: {e}")))?;
 
     // Close the slave side in the parent process
     drop(pair.slave);
@@ -250,12 +1107,12 @@ fn spawn_pty(
     let reader = pair
         .master
         .try_clone_reader()
-        .map_err(|e| format!("Failed to clone PTY reader: {e}"))?;
+        .map_err(|e| AppError::internal(format!("Failed to clone PTY reader: {e}")))?;
 
     let writer = pair
         .master
         .take_writer()
-        .map_err(|e| format!("Failed to get PTY writer: {e}"))?;
+        .map_err(|e| AppError::internal(format!("Failed to get PTY writer: {e}")))?;
 
     // Store the session
     {
@@ -279,19 +1136,58 @@ fn spawn_pty(
         let mut reader = reader;
         let mut buf = [0u8; 8192];
         let engine = base64::engine::general_purpose::STANDARD;
-        loop {
+        let exit = loop {
             match reader.read(&mut buf) {
                 Ok(0) => {
-                    let _ = app_handle.emit(&format!("pty-exit-{sid}"), ());
-                    break;
+                    // EOF: wait on the child to tell a clean logout from a
+                    // nonzero exit (failed command, killed remote shell).
+                    break match child.wait() {
+                        Ok(status) if status.success() => PtyExit {
+                            reason: PtyExitReason::Eof,
+                            code: None,
+                            message: None,
+                        },
+                        Ok(status) => PtyExit {
+                            reason: PtyExitReason::ChildExit,
+                            code: Some(status.exit_code()),
+                            message: None,
+                        },
+                        Err(err) => PtyExit {
+                            reason: PtyExitReason::ChildExit,
+                            code: None,
+                            message: Some(err.to_string()),
+                        },
+                    };
                 }
                 Ok(n) => {
                     let encoded = engine.encode(&buf[..n]);
                     let _ = app_handle.emit(&format!("pty-output-{sid}"), encoded);
                 }
-                Err(_) => {
-                    let _ = app_handle.emit(&format!("pty-exit-{sid}"), ());
-                    break;
+                Err(err) => {
+                    break PtyExit {
+                        reason: PtyExitReason::ReadError,
+                        code: None,
+                        message: Some(err.to_string()),
+                    };
+                }
+            }
+        };
+        let _ = app_handle.emit(&format!("pty-exit-{sid}"), exit);
+
+        // Tell any broadcast group this session belonged to that the member
+        // dropped, so the UI can mark it while still fanning input to survivors.
+        if let Some(state) = app_handle.try_state::<AppState>() {
+            if let Ok(groups) = state.session_groups.lock() {
+                for (group_id, members) in groups.iter() {
+                    if members.iter().any(|member| member == &sid) {
+                        let _ = app_handle.emit(
+                            "pty-group-member-exit",
+                            GroupMemberExit {
+                                group_id: group_id.clone(),
+                                session_id: sid.clone(),
+                            },
+                        );
+                    }
                 }
             }
         }
@@ -301,22 +1197,24 @@ fn spawn_pty(
 }
 
 #[tauri::command]
-fn write_pty(state: tauri::State<'_, AppState>, session_id: String, data: String) -> Result<(), String> {
+fn write_pty(state: tauri::State<'_, AppState>, session_id: String, data: String) -> Result<(), AppError> {
     let mut sessions = state
         .pty_sessions
         .lock()
         .map_err(|_| "pty state poisoned")?;
     let session = sessions
         .get_mut(&session_id)
-        .ok_or("Session not found")?;
+        .ok_or_else(|| AppError::not_found("Session not found"))?;
+    // Map the io::Error through the typed taxonomy so a broken pipe / dropped
+    // connection surfaces as HostUnreachable/Io rather than a flattened Internal.
     session
         .writer
         .write_all(data.as_bytes())
-        .map_err(|e| format!("Write failed: {e}"))?;
+        .context("while writing to the session")?;
     session
         .writer
         .flush()
-        .map_err(|e| format!("Flush failed: {e}"))?;
+        .context("while flushing the session")?;
     Ok(())
 }
 
@@ -326,12 +1224,14 @@ fn resize_pty(
     session_id: String,
     rows: u16,
     cols: u16,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let sessions = state
         .pty_sessions
         .lock()
         .map_err(|_| "pty state poisoned")?;
-    let session = sessions.get(&session_id).ok_or("Session not found")?;
+    let session = sessions
+        .get(&session_id)
+        .ok_or_else(|| AppError::not_found("Session not found"))?;
     session
         .master
         .resize(PtySize {
@@ -345,7 +1245,7 @@ fn resize_pty(
 }
 
 #[tauri::command]
-fn close_pty(state: tauri::State<'_, AppState>, session_id: String) -> Result<(), String> {
+fn close_pty(state: tauri::State<'_, AppState>, session_id: String) -> Result<(), AppError> {
     let mut sessions = state
         .pty_sessions
         .lock()
@@ -354,121 +1254,225 @@ fn close_pty(state: tauri::State<'_, AppState>, session_id: String) -> Result<()
     Ok(())
 }
 
-#[tauri::command]
-fn list_directory(path: Option<String>) -> Result<Vec<DirEntry>, String> {
-    let dir = path
-        .map(PathBuf::from)
-        .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/")));
-
-    let entries = fs::read_dir(&dir).map_err(|e| format!("Failed to read directory: {e}"))?;
-
-    let mut dirs = Vec::new();
-    let mut files = Vec::new();
-
-    for entry in entries {
-        let entry = entry.map_err(|e| format!("Failed to read entry: {e}"))?;
-        let name = entry.file_name().to_string_lossy().into_owned();
-        let is_dir = entry
-            .file_type()
-            .map(|ft| ft.is_dir())
-            .unwrap_or(false);
-
-        if is_dir {
-            dirs.push(DirEntry { name, is_dir: true });
-        } else {
-            files.push(DirEntry { name, is_dir: false });
-        }
-    }
+/// Per-member failure from a `write_group` fan-out: the broadcast continues to
+/// the remaining members and every failure is collected here rather than
+/// aborting on the first.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GroupWriteError {
+    session_id: String,
+    message: String,
+}
 
-    dirs.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-    files.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-    dirs.append(&mut files);
+/// Payload of the `pty-group-member-exit` event: which group a dropped session
+/// belonged to.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GroupMemberExit {
+    group_id: String,
+    session_id: String,
+}
 
-    Ok(dirs)
+#[tauri::command]
+fn create_group(
+    state: tauri::State<'_, AppState>,
+    group_id: String,
+    session_ids: Vec<String>,
+) -> Result<(), AppError> {
+    let mut groups = state.session_groups.lock().map_err(|_| "group state poisoned")?;
+    groups.insert(group_id, session_ids);
+    Ok(())
 }
 
 #[tauri::command]
-fn rename_entry(old_path: String, new_path: String) -> Result<(), String> {
-    let src = Path::new(&old_path);
-    if !src.exists() {
-        return Err(format!("Source does not exist: {old_path}"));
-    }
-    let dest = Path::new(&new_path);
-    if dest.exists() {
-        return Err(format!("Destination already exists: {new_path}"));
+fn add_to_group(
+    state: tauri::State<'_, AppState>,
+    group_id: String,
+    session_id: String,
+) -> Result<(), AppError> {
+    let mut groups = state.session_groups.lock().map_err(|_| "group state poisoned")?;
+    let members = groups.entry(group_id).or_default();
+    if !members.contains(&session_id) {
+        members.push(session_id);
     }
-    fs::rename(src, dest).map_err(|e| format!("Rename failed: {e}"))
+    Ok(())
 }
 
 #[tauri::command]
-fn delete_entry(path: String) -> Result<(), String> {
-    let p = Path::new(&path);
-    if !p.exists() {
-        return Err(format!("Path does not exist: {path}"));
+fn remove_from_group(
+    state: tauri::State<'_, AppState>,
+    group_id: String,
+    session_id: String,
+) -> Result<(), AppError> {
+    let mut groups = state.session_groups.lock().map_err(|_| "group state poisoned")?;
+    if let Some(members) = groups.get_mut(&group_id) {
+        members.retain(|member| member != &session_id);
     }
-    if p.is_dir() {
-        fs::remove_dir_all(p).map_err(|e| format!("Delete directory failed: {e}"))
-    } else {
-        fs::remove_file(p).map_err(|e| format!("Delete file failed: {e}"))
+    Ok(())
+}
+
+#[tauri::command]
+fn write_group(
+    state: tauri::State<'_, AppState>,
+    group_id: String,
+    data: String,
+) -> Result<Vec<GroupWriteError>, AppError> {
+    let members = {
+        let groups = state.session_groups.lock().map_err(|_| "group state poisoned")?;
+        groups
+            .get(&group_id)
+            .cloned()
+            .ok_or_else(|| AppError::not_found("Group not found"))?
+    };
+
+    // Fan out under a single `pty_sessions` lock so one slow member can't
+    // interleave with a concurrent writer mid-broadcast.
+    let mut sessions = state.pty_sessions.lock().map_err(|_| "pty state poisoned")?;
+    let mut failures = Vec::new();
+    for session_id in members {
+        let Some(session) = sessions.get_mut(&session_id) else {
+            failures.push(GroupWriteError {
+                session_id,
+                message: "session not found".to_string(),
+            });
+            continue;
+        };
+        if let Err(err) = session
+            .writer
+            .write_all(data.as_bytes())
+            .and_then(|_| session.writer.flush())
+        {
+            failures.push(GroupWriteError {
+                session_id,
+                message: err.to_string(),
+            });
+        }
     }
+    Ok(failures)
 }
 
 #[tauri::command]
-fn move_entry(src: String, dest_dir: String) -> Result<(), String> {
-    let src_path = Path::new(&src);
-    if !src_path.exists() {
-        return Err(format!("Source does not exist: {src}"));
+fn list_directory(
+    state: tauri::State<'_, AppState>,
+    path: Option<String>,
+    profile_id: Option<String>,
+) -> Result<Vec<DirEntry>, AppError> {
+    let dir = match path {
+        Some(path) => path,
+        // No explicit path: the local cwd is meaningless on a remote host, so
+        // start SFTP browsing at ".", which the server resolves to the login
+        // (home) directory.
+        None if profile_id.is_some() => ".".to_string(),
+        None => std::env::current_dir()
+            .map(|cwd| cwd.to_string_lossy().into_owned())
+            .unwrap_or_else(|_| "/".to_string()),
+    };
+
+    let backend = vfs_for(&state, profile_id)?;
+    let mut entries = backend.read_dir(&dir)?;
+
+    // Directories first, then files, each alphabetised case-insensitively.
+    entries.sort_by(|a, b| {
+        b.is_dir
+            .cmp(&a.is_dir)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+
+    Ok(entries)
+}
+
+#[tauri::command]
+fn rename_entry(
+    state: tauri::State<'_, AppState>,
+    old_path: String,
+    new_path: String,
+    profile_id: Option<String>,
+) -> Result<(), AppError> {
+    let backend = vfs_for(&state, profile_id)?;
+    if backend.stat(&new_path).is_ok() {
+        return Err(FsError::AlreadyExists.into());
     }
-    let file_name = src_path
+    backend
+        .rename(&old_path, &new_path)
+        .with_context(|| format!("while renaming {old_path}"))
+}
+
+#[tauri::command]
+fn delete_entry(
+    state: tauri::State<'_, AppState>,
+    path: String,
+    profile_id: Option<String>,
+) -> Result<(), AppError> {
+    let backend = vfs_for(&state, profile_id)?;
+    backend
+        .remove(&path)
+        .with_context(|| format!("while deleting {path}"))
+}
+
+#[tauri::command]
+fn move_entry(
+    state: tauri::State<'_, AppState>,
+    src: String,
+    dest_dir: String,
+    profile_id: Option<String>,
+) -> Result<(), AppError> {
+    let file_name = Path::new(&src)
         .file_name()
-        .ok_or_else(|| "Cannot determine file name".to_string())?;
+        .ok_or(FsError::InvalidPath)?
+        .to_string_lossy()
+        .into_owned();
     let dest_path = Path::new(&dest_dir).join(file_name);
-    if dest_path.exists() {
-        return Err(format!(
-            "Destination already exists: {}",
-            dest_path.display()
-        ));
+    let dest = dest_path.to_string_lossy().into_owned();
+
+    let backend = vfs_for(&state, profile_id)?;
+    if backend.stat(&dest).is_ok() {
+        return Err(FsError::AlreadyExists.into());
     }
-    fs::rename(src_path, &dest_path).map_err(|e| format!("Move failed: {e}"))
+    backend
+        .rename(&src, &dest)
+        .with_context(|| format!("while moving {src} into {dest_dir}"))
 }
 
 #[tauri::command]
-fn create_file(path: String) -> Result<(), String> {
-    OpenOptions::new()
-        .write(true)
-        .create_new(true)
-        .open(&path)
-        .map_err(|e| format!("Create file failed: {e}"))?;
-    Ok(())
+fn create_file(
+    state: tauri::State<'_, AppState>,
+    path: String,
+    profile_id: Option<String>,
+) -> Result<(), AppError> {
+    let backend = vfs_for(&state, profile_id)?;
+    backend
+        .create_file(&path)
+        .with_context(|| format!("while creating file {path}"))
 }
 
 #[tauri::command]
-fn create_dir(path: String) -> Result<(), String> {
-    fs::create_dir(&path).map_err(|e| format!("Create directory failed: {e}"))
+fn create_dir(
+    state: tauri::State<'_, AppState>,
+    path: String,
+    profile_id: Option<String>,
+) -> Result<(), AppError> {
+    let backend = vfs_for(&state, profile_id)?;
+    backend
+        .create_dir(&path)
+        .with_context(|| format!("while creating directory {path}"))
 }
 
 #[tauri::command]
-fn open_file_default(path: String) -> Result<(), String> {
-    Command::new("open")
-        .arg(&path)
-        .spawn()
-        .map_err(|e| format!("Failed to open file: {e}"))?;
+fn open_file_default(path: String) -> Result<(), AppError> {
+    let mut command = Command::new("open");
+    command.arg(&path);
+    spawn_command(&mut command, "open").with_context(|| format!("while opening {path}"))?;
     Ok(())
 }
 
 #[tauri::command]
-fn open_in_vscode(path: String) -> Result<(), String> {
-    Command::new("code")
-        .arg(&path)
-        .spawn()
-        .map_err(|e| format!("Failed to open in VS Code: {e}"))?;
+fn open_in_vscode(path: String) -> Result<(), AppError> {
+    let mut command = Command::new("code");
+    command.arg(&path);
+    spawn_command(&mut command, "code").with_context(|| format!("while opening {path} in VS Code"))?;
     Ok(())
 }
 
-fn escape_shell(input: &str) -> String {
-    input.replace('"', "\\\"").replace('\'', "'\\''")
-}
-
 fn profile_store_path(app: &tauri::AppHandle) -> PathBuf {
     if let Ok(directory) = app.path().app_data_dir() {
         return directory.join("profiles.json");
@@ -580,6 +1584,9 @@ pub fn run() {
             list_profiles,
             upsert_profile,
             add_profile_from_csv,
+            export_profiles_toml,
+            import_profiles_toml,
+            import_ssh_config,
             connect_profile,
             open_local_terminal,
             open_vscode,
@@ -587,6 +1594,10 @@ pub fn run() {
             write_pty,
             resize_pty,
             close_pty,
+            create_group,
+            add_to_group,
+            remove_from_group,
+            write_group,
             list_directory,
             rename_entry,
             delete_entry,
@@ -603,3 +1614,81 @@ pub fn run() {
         panic!("error while running tauri application: {error}");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(name: &str, host: &str, user: Option<&str>, port: Option<u16>) -> ServerProfile {
+        ServerProfile {
+            id: String::new(),
+            name: name.to_string(),
+            host: host.to_string(),
+            user: user.map(str::to_string),
+            port,
+            password: None,
+            tags: Vec::new(),
+            favorite: false,
+            last_used_at: None,
+            command_script: None,
+        }
+    }
+
+    #[test]
+    fn parse_ssh_config_skips_wildcard_hosts() {
+        let config = "\
+Host *
+  User default
+
+Host box
+  HostName box.example.com
+";
+        let parsed = parse_ssh_config(config);
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].name, "box");
+        assert_eq!(parsed[0].host, "box.example.com");
+    }
+
+    #[test]
+    fn parse_ssh_config_applies_hostname_and_port() {
+        let config = "\
+Host web
+  HostName 10.0.0.5
+  User deploy
+  Port 2222
+";
+        let parsed = parse_ssh_config(config);
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].host, "10.0.0.5");
+        assert_eq!(parsed[0].user.as_deref(), Some("deploy"));
+        assert_eq!(parsed[0].port, Some(2222));
+    }
+
+    #[test]
+    fn merge_profiles_replaces_match_in_place_keeping_id() {
+        let mut existing = vec![ServerProfile {
+            id: "keep-me".to_string(),
+            favorite: true,
+            ..profile("old", "host.example.com", Some("admin"), Some(22))
+        }];
+
+        // Same (host, user, port) — port omitted still matches the explicit :22.
+        let incoming = vec![profile("renamed", "host.example.com", Some("admin"), None)];
+        merge_profiles(&mut existing, incoming);
+
+        assert_eq!(existing.len(), 1);
+        assert_eq!(existing[0].id, "keep-me");
+        assert_eq!(existing[0].name, "renamed");
+    }
+
+    #[test]
+    fn merge_profiles_inserts_new_and_assigns_id() {
+        let mut existing = vec![profile("a", "a.example.com", Some("root"), Some(22))];
+        existing[0].id = "a-id".to_string();
+
+        merge_profiles(&mut existing, vec![profile("b", "b.example.com", Some("root"), Some(22))]);
+
+        assert_eq!(existing.len(), 2);
+        assert!(!existing[1].id.is_empty());
+    }
+}